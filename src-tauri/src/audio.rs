@@ -1,5 +1,5 @@
 use std::{
-  collections::{HashMap, VecDeque},
+  collections::HashMap,
   io::Cursor,
   sync::{
     atomic::{AtomicBool, Ordering},
@@ -11,16 +11,17 @@ use coreaudio::audio_unit::audio_format::LinearPcmFlags;
 use coreaudio::audio_unit::render_callback::{self, data};
 use coreaudio::audio_unit::{AudioUnit, Element, SampleFormat, Scope, StreamFormat};
 use coreaudio::sys::{
-  kAudioHardwarePropertyDefaultOutputDevice, kAudioHardwarePropertyDevices,
+  kAudioHardwarePropertyDefaultInputDevice, kAudioHardwarePropertyDefaultOutputDevice,
+  kAudioHardwarePropertyDevices,
   kAudioObjectPropertyElementMain, kAudioObjectPropertyScopeGlobal,
-  kAudioObjectPropertyScopeOutput, kAudioObjectSystemObject,
+  kAudioObjectPropertyScopeInput, kAudioObjectPropertyScopeOutput, kAudioObjectSystemObject,
   kAudioDevicePropertyDeviceNameCFString,
   kAudioDevicePropertyStreams, AudioDeviceID, AudioObjectGetPropertyData,
   AudioObjectGetPropertyDataSize, AudioObjectPropertyAddress,
 };
 use rodio::Decoder;
-use serde::Serialize;
-use tauri::State;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
 
 pub type PlayerId = u64;
 
@@ -36,6 +37,15 @@ impl AudioController {
     Self { tx }
   }
 
+  /// デフォルト出力デバイス変更・デバイス一覧変更のCoreAudioプロパティリスナーを登録する。
+  /// アプリのセットアップ完了後、`AppHandle`が得られてから一度だけ呼ぶ
+  pub fn install_hotplug_listeners(&self, app_handle: AppHandle) {
+    install_hotplug_listeners(self.tx.clone(), app_handle.clone());
+    // 再生終端検知（`audio://playback-finished`）のイベント送出に使うため、
+    // オーディオスレッド側にも`AppHandle`を渡しておく
+    let _ = self.tx.send(AudioCommand::SetAppHandle { app_handle });
+  }
+
   fn call<R>(
     &self,
     build: impl FnOnce(mpsc::Sender<Result<R, String>>) -> AudioCommand,
@@ -68,6 +78,7 @@ enum AudioCommand {
     player_id: PlayerId,
     bytes: Vec<u8>,
     file_name: String,
+    quality: ResampleQuality,
     respond_to: mpsc::Sender<Result<AudioPlayerState, String>>,
   },
   TogglePlayback {
@@ -87,8 +98,36 @@ enum AudioCommand {
     sample_rate: u32,
     channels: u16,
     samples: Vec<f32>,
+    quality: ResampleQuality,
+    respond_to: mpsc::Sender<Result<AudioPlayerState, String>>,
+  },
+  StartRecording {
+    player_id: PlayerId,
+    device_id: String,
+    respond_to: mpsc::Sender<Result<AudioPlayerState, String>>,
+  },
+  StopRecording {
+    player_id: PlayerId,
+    respond_to: mpsc::Sender<Result<Vec<u8>, String>>,
+  },
+  /// システムのデフォルト出力デバイスが変わった通知（CoreAudioのプロパティリスナーから発火）
+  DefaultDeviceChanged,
+  Seek {
+    player_id: PlayerId,
+    position_secs: f64,
     respond_to: mpsc::Sender<Result<AudioPlayerState, String>>,
   },
+  /// `audio://playback-finished`イベント送出用に`AppHandle`をオーディオスレッドへ渡す
+  SetAppHandle { app_handle: AppHandle },
+}
+
+/// リサンプリング品質。`Fast`は線形補間、`High`は帯域制限sincフィルタを使う
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResampleQuality {
+  Fast,
+  #[default]
+  High,
 }
 
 #[derive(Debug, Serialize)]
@@ -97,6 +136,12 @@ pub struct AudioOutputDevice {
   pub name: String,
 }
 
+#[derive(Debug, Serialize)]
+pub struct AudioInputDevice {
+  pub id: String,
+  pub name: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AudioPlayerState {
   pub player_id: PlayerId,
@@ -106,36 +151,191 @@ pub struct AudioPlayerState {
   pub is_playing: bool,
   pub is_paused: bool,
   pub is_empty: bool,
+  pub is_recording: bool,
+  pub position_secs: f64,
+  pub duration_secs: f64,
 }
 
-/// 共有オーディオバッファ
+/// レンダーコールバックが一度に扱うチャンネル数の上限（5.1/7.1まで想定）
+const MAX_RENDER_CHANNELS: usize = 8;
+
+/// 共有オーディオバッファ。レンダーコールバック（リアルタイムスレッド）からは
+/// 一切ロックせずに読み出せるよう、単一生産者・単一消費者(SPSC)のロックフリー
+/// リングバッファとして実装する。生産者はオーディオコマンドスレッド（`push_samples`/
+/// `clear`）、消費者はCoreAudioのレンダーコールバック（`pop_into`）のみが呼ぶ前提。
+/// `head`・`consumed_total`・`drained`は消費者のみが書き込む値であり、`clear`は
+/// それらを直接書き換えず、消費者に次回`pop_into`でのリセットを依頼するだけに留める。
 struct SharedBuffer {
-  samples: Mutex<VecDeque<f32>>,
+  /// 固定長のリングバッファ本体。`head`/`tail`がインデックスをmod capacityで指す
+  data: std::cell::UnsafeCell<Vec<f32>>,
+  capacity: usize,
+  /// 消費者（レンダーコールバック）が次に読む位置（単調増加、capで折り返さない）。
+  /// `pop_into`/`apply_pending_reset`だけが書き込む
+  head: std::sync::atomic::AtomicUsize,
+  /// 生産者が次に書く位置（単調増加）
+  tail: std::sync::atomic::AtomicUsize,
   is_paused: AtomicBool,
+  /// バッファ満杯のため取りこぼしたサンプル数（バックプレッシャーの可視化用）
+  overflowed: std::sync::atomic::AtomicUsize,
+  /// 直近のリセット以降に実際に消費（pop）されたサンプル数。再生位置の算出に使う。
+  /// `pop_into`/`apply_pending_reset`だけが書き込む
+  consumed_total: std::sync::atomic::AtomicUsize,
+  /// 一時停止中でないにもかかわらずバッファが空になった（アンダーラン＝再生終端に
+  /// 達した）ことを示すフラグ。`clear`のリセット依頼が適用されるまで立ったままになる。
+  /// `pop_into`/`apply_pending_reset`だけが書き込む
+  drained: AtomicBool,
+  /// `clear`から`pop_into`への「ここまで読み捨てて`head`をここへ進めてほしい」という
+  /// 依頼値。`tail`の値を+1した値で格納し、0は「依頼なし」を表す番兵とする
+  reset_to: std::sync::atomic::AtomicUsize,
 }
 
+// UnsafeCellへのアクセスはSPSCの契約（生産者はpush_samples/clearのみ、消費者は
+// pop_intoのみを呼ぶ）により排他される。
+unsafe impl Sync for SharedBuffer {}
+
 impl SharedBuffer {
+  /// 48kHzステレオで約5.5秒分。曲全体ではなく、再生の先読み分だけを保持する
+  /// ストリーミングウィンドウとしてのサイズ（`PlayerInner::refill`が随時補充する）
+  const CAPACITY: usize = 1 << 19;
+
   fn new() -> Arc<Self> {
     Arc::new(Self {
-      samples: Mutex::new(VecDeque::new()),
+      data: std::cell::UnsafeCell::new(vec![0.0; Self::CAPACITY]),
+      capacity: Self::CAPACITY,
+      head: std::sync::atomic::AtomicUsize::new(0),
+      tail: std::sync::atomic::AtomicUsize::new(0),
       is_paused: AtomicBool::new(false),
+      overflowed: std::sync::atomic::AtomicUsize::new(0),
+      consumed_total: std::sync::atomic::AtomicUsize::new(0),
+      drained: AtomicBool::new(false),
+      reset_to: std::sync::atomic::AtomicUsize::new(0),
     })
   }
 
-  fn push_samples(&self, data: &[f32]) {
-    if let Ok(mut buf) = self.samples.lock() {
-      buf.extend(data.iter().copied());
+  /// 生産者側: 末尾にサンプルを積む。バッファが満杯の場合は古いデータを上書きせず、
+  /// 新しいサンプルの方を捨てて`overflowed`を増やす（デコードが再生を追い越した場合のポリシー）
+  fn push_samples(&self, samples: &[f32]) {
+    use std::sync::atomic::Ordering as Ord;
+
+    let mut tail = self.tail.load(Ord::Relaxed);
+    let head = self.head.load(Ord::Acquire);
+
+    for &sample in samples {
+      if tail.wrapping_sub(head) >= self.capacity {
+        self.overflowed.fetch_add(1, Ord::Relaxed);
+        continue;
+      }
+      unsafe {
+        (*self.data.get())[tail % self.capacity] = sample;
+      }
+      tail = tail.wrapping_add(1);
+    }
+
+    self.tail.store(tail, Ord::Release);
+  }
+
+  /// 消費者側（レンダーコールバック）: `clear`からのリセット依頼があれば`head`を
+  /// 進めて適用する。依頼時点の`tail`まで読み捨てるだけなので、依頼後に積まれた
+  /// 新しいサンプルは失われない。一時停止中も含め、レンダーコールバックの毎回の
+  /// 呼び出しで必ず実行される必要がある（`pop_into`が呼ばれない一時停止中に
+  /// 依頼が取り残されないようにするため）
+  fn apply_pending_reset(&self) {
+    use std::sync::atomic::Ordering as Ord;
+    let reset_request = self.reset_to.swap(0, Ord::AcqRel);
+    if reset_request != 0 {
+      self.head.store(reset_request - 1, Ord::Release);
+      self.consumed_total.store(0, Ord::Release);
+      self.drained.store(false, Ord::Release);
+    }
+  }
+
+  /// 消費者側（レンダーコールバック）: `dest`をちょうど埋める。データが足りない
+  /// 区間はアンダーランとして無音（0.0）で埋める
+  fn pop_into(&self, dest: &mut [f32]) {
+    use std::sync::atomic::Ordering as Ord;
+
+    self.apply_pending_reset();
+
+    let mut head = self.head.load(Ord::Relaxed);
+    let tail = self.tail.load(Ord::Acquire);
+    let mut popped = 0usize;
+    let mut underran = false;
+
+    for slot in dest.iter_mut() {
+      if head == tail {
+        *slot = 0.0;
+        underran = true;
+      } else {
+        *slot = unsafe { (*self.data.get())[head % self.capacity] };
+        head = head.wrapping_add(1);
+        popped += 1;
+      }
+    }
+
+    self.head.store(head, Ord::Release);
+    if popped > 0 {
+      self.consumed_total.fetch_add(popped, Ord::Relaxed);
+    }
+    if underran {
+      self.drained.store(true, Ord::Release);
     }
   }
 
+  /// 生産者側: バッファを空にしたい旨を消費者（`pop_into`）へ依頼する。`head`は
+  /// 消費者だけが書き込むフィールドのため、ここでは直接書き換えない
   fn clear(&self) {
-    if let Ok(mut buf) = self.samples.lock() {
-      buf.clear();
+    use std::sync::atomic::Ordering as Ord;
+    let tail = self.tail.load(Ord::Acquire);
+    self.reset_to.store(tail.wrapping_add(1), Ord::Release);
+  }
+
+  /// 一時停止中でないのにバッファが空になった（＝再生が終端まで消費された）かどうか。
+  /// `clear`のリセット依頼がまだレンダーコールバックに適用されていない間は、適用後に
+  /// `false`になることが確定しているため、ここで先取りして`false`を返す
+  fn is_draining(&self) -> bool {
+    if self.has_pending_reset() {
+      return false;
+    }
+    self.drained.load(Ordering::Acquire)
+  }
+
+  /// 直近の`clear`以降の再生経過秒数（チャンネル数とサンプルレートから換算）。
+  /// リセット依頼が未適用の間は、適用後に`consumed_total`が0になることが確定して
+  /// いるため、レンダーコールバックの実行を待たずに0を返す
+  fn consumed_secs(&self, channels: usize, sample_rate: f64) -> f64 {
+    use std::sync::atomic::Ordering as Ord;
+    if self.has_pending_reset() {
+      return 0.0;
     }
+    let consumed = self.consumed_total.load(Ord::Acquire) as f64;
+    consumed / channels.max(1) as f64 / sample_rate
   }
 
+  /// リセット依頼がまだレンダーコールバックに適用されていない間は、依頼時点の`tail`
+  /// （`reset_to`が指す値）を仮想的な`head`として扱い、その後に積まれた新しいサンプルの
+  /// 有無だけで空かどうかを判定する。こうすることで、`seek`/`Stop`が`clear`直後に
+  /// `state()`を同期的に読んでも、レンダーコールバックの次回実行を待たずに正しい値になる
   fn is_empty(&self) -> bool {
-    self.samples.lock().map(|b| b.is_empty()).unwrap_or(true)
+    use std::sync::atomic::Ordering as Ord;
+    let reset_request = self.reset_to.load(Ord::Acquire);
+    if reset_request != 0 {
+      let pending_head = reset_request - 1;
+      return self.tail.load(Ord::Acquire) == pending_head;
+    }
+    self.head.load(Ord::Acquire) == self.tail.load(Ord::Acquire)
+  }
+
+  /// 適用待ちの`clear`リセット依頼があるかどうか
+  fn has_pending_reset(&self) -> bool {
+    self.reset_to.load(Ordering::Acquire) != 0
+  }
+
+  /// これ以上詰めるとオーバーフローする手前までの空き容量
+  fn free_len(&self) -> usize {
+    use std::sync::atomic::Ordering as Ord;
+    let head = self.head.load(Ord::Acquire);
+    let tail = self.tail.load(Ord::Acquire);
+    self.capacity.saturating_sub(tail.wrapping_sub(head))
   }
 
   fn pause(&self) {
@@ -151,9 +351,36 @@ impl SharedBuffer {
   }
 }
 
+/// 録音中にキャプチャしたサンプルを貯めるバッファ
+struct RecordBuffer {
+  samples: Mutex<Vec<f32>>,
+  channels: u16,
+}
+
+impl RecordBuffer {
+  fn new(channels: u16) -> Arc<Self> {
+    Arc::new(Self {
+      samples: Mutex::new(Vec::new()),
+      channels,
+    })
+  }
+
+  fn push_samples(&self, data: &[f32]) {
+    if let Ok(mut buf) = self.samples.lock() {
+      buf.extend_from_slice(data);
+    }
+  }
+
+  fn take_samples(&self) -> Vec<f32> {
+    self.samples.lock().map(|mut b| std::mem::take(&mut *b)).unwrap_or_default()
+  }
+}
+
 struct AudioEngineInner {
   next_player_id: PlayerId,
   players: HashMap<PlayerId, PlayerInner>,
+  /// 再生終端イベントの送出に使う。`SetAppHandle`コマンドで受け取り次第セットされる
+  app_handle: Option<AppHandle>,
 }
 
 struct PlayerInner {
@@ -163,6 +390,30 @@ struct PlayerInner {
   sample_rate: f64,
   mp3: Option<Arc<[u8]>>,
   file_name: String,
+  recording_unit: Option<AudioUnit>,
+  record_buffer: Option<Arc<RecordBuffer>>,
+  record_sample_rate: f64,
+  resample_quality: ResampleQuality,
+  output_channels: u16,
+  /// デコード済みのPCM。MP3ロード時に一度だけデコードし、再生・シークで使い回す
+  decoded: Option<Arc<DecodedAudio>>,
+  /// 現在バッファに積まれている区間の先頭が、曲全体の何秒目に当たるか（シーク起点）
+  position_offset_secs: f64,
+  /// 今回の再生で`audio://playback-finished`を送出済みかどうか（多重送出防止）
+  finished_notified: bool,
+  /// 変換済みだがまだリングバッファに積み切れていないサンプル（容量を超える曲用）
+  pending_samples: Vec<f32>,
+  /// `pending_samples`のうち、次に積む位置
+  pending_cursor: usize,
+  /// `pending_samples`を全てリングバッファへ積み終えたか（終端判定に使う）
+  fully_queued: bool,
+}
+
+/// デコード済みのPCMサンプルとそのフォーマット
+struct DecodedAudio {
+  samples: Vec<f32>,
+  sample_rate: u32,
+  channels: u16,
 }
 
 impl Default for AudioEngineInner {
@@ -170,6 +421,7 @@ impl Default for AudioEngineInner {
     Self {
       next_player_id: 0,
       players: HashMap::new(),
+      app_handle: None,
     }
   }
 }
@@ -193,6 +445,17 @@ impl AudioEngineInner {
         sample_rate: 48000.0,
         mp3: None,
         file_name: String::new(),
+        recording_unit: None,
+        record_buffer: None,
+        record_sample_rate: 48000.0,
+        resample_quality: ResampleQuality::default(),
+        output_channels: 2,
+        decoded: None,
+        position_offset_secs: 0.0,
+        finished_notified: false,
+        pending_samples: Vec::new(),
+        pending_cursor: 0,
+        fully_queued: true,
       },
     );
 
@@ -204,6 +467,9 @@ impl AudioEngineInner {
       if let Some(mut au) = player.audio_unit.take() {
         let _ = au.stop();
       }
+      if let Some(mut au) = player.recording_unit.take() {
+        let _ = au.stop();
+      }
       Ok(())
     } else {
       Err(format!("player not found: {player_id}"))
@@ -232,6 +498,18 @@ impl PlayerInner {
       None => (false, true),
     };
 
+    let elapsed_secs = self
+      .buffer
+      .as_ref()
+      .map(|buf| buf.consumed_secs(self.output_channels as usize, self.sample_rate))
+      .unwrap_or(0.0);
+
+    let duration_secs = self
+      .decoded
+      .as_ref()
+      .map(|d| d.samples.len() as f64 / (d.channels as f64 * d.sample_rate as f64))
+      .unwrap_or(0.0);
+
     AudioPlayerState {
       player_id,
       device_id: self.device_id.clone(),
@@ -240,7 +518,51 @@ impl PlayerInner {
       is_playing: !is_paused && !is_empty,
       is_paused,
       is_empty,
+      is_recording: self.recording_unit.is_some(),
+      position_secs: (self.position_offset_secs + elapsed_secs).min(duration_secs.max(0.0)),
+      duration_secs,
+    }
+  }
+
+  fn start_recording(&mut self, device_id: String) -> Result<(), String> {
+    if self.recording_unit.is_some() {
+      return Err("既に録音中です".to_string());
     }
+
+    let device_id = if device_id == "default" {
+      get_default_input_device()?
+    } else {
+      device_id
+        .parse::<AudioDeviceID>()
+        .map_err(|_| format!("不正なdevice idです: {}", device_id))?
+    };
+
+    let (audio_unit, sample_rate, record_buffer) = create_audio_input_unit_for_device(device_id)?;
+
+    self.recording_unit = Some(audio_unit);
+    self.record_buffer = Some(record_buffer);
+    self.record_sample_rate = sample_rate;
+    Ok(())
+  }
+
+  fn stop_recording(&mut self) -> Result<Vec<u8>, String> {
+    let mut au = self
+      .recording_unit
+      .take()
+      .ok_or_else(|| "録音中ではありません".to_string())?;
+    let _ = au.stop();
+
+    let record_buffer = self
+      .record_buffer
+      .take()
+      .ok_or_else(|| "録音バッファがありません".to_string())?;
+
+    let samples = record_buffer.take_samples();
+    Ok(encode_wav_pcm16(
+      &samples,
+      self.record_sample_rate as u32,
+      record_buffer.channels,
+    ))
   }
 
   fn ensure_output(&mut self) -> Result<(), String> {
@@ -257,11 +579,12 @@ impl PlayerInner {
         .map_err(|_| format!("不正なdevice idです: {}", self.device_id))?
     };
 
-    let (audio_unit, sample_rate, buffer) = create_audio_unit_for_device(device_id)?;
+    let (audio_unit, sample_rate, channels, buffer) = create_audio_unit_for_device(device_id, None)?;
 
     self.audio_unit = Some(audio_unit);
     self.buffer = Some(buffer);
     self.sample_rate = sample_rate;
+    self.output_channels = channels;
     Ok(())
   }
 
@@ -271,17 +594,65 @@ impl PlayerInner {
       let _ = au.stop();
     }
     self.buffer = None;
+    // 古いバッファ向けの積み残しを新しいバッファへ引き継がないよう破棄する
+    self.clear_pending();
 
     self.device_id = device_id;
     self.ensure_output()
   }
 
-  fn load_mp3(&mut self, bytes: Vec<u8>, file_name: String) -> Result<(), String> {
+  /// デフォルト出力デバイスが切り替わった際に、バッファの中身を保持したまま
+  /// AudioUnitだけを新しいデバイスへ繋ぎ直す。`device_id`が`"default"`のプレイヤーのみが対象
+  fn rebuild_default_output(&mut self) -> Result<(), String> {
+    if self.device_id != "default" {
+      return Ok(());
+    }
+
+    let was_paused = self.buffer.as_ref().map(|b| b.is_paused()).unwrap_or(true);
+
+    if let Some(mut au) = self.audio_unit.take() {
+      let _ = au.stop();
+    }
+
+    let device_id = get_default_output_device()?;
+    let existing_buffer = self.buffer.clone();
+    let (audio_unit, sample_rate, channels, buffer) =
+      create_audio_unit_for_device(device_id, existing_buffer)?;
+
+    self.audio_unit = Some(audio_unit);
+    self.buffer = Some(buffer);
+    self.sample_rate = sample_rate;
+    self.output_channels = channels;
+
+    if !was_paused {
+      if let Some(au) = self.audio_unit.as_mut() {
+        let _ = au.start();
+      }
+    }
+
+    Ok(())
+  }
+
+  fn load_mp3(&mut self, bytes: Vec<u8>, file_name: String, quality: ResampleQuality) -> Result<(), String> {
     self.mp3 = Some(Arc::from(bytes));
     self.file_name = file_name;
+    self.resample_quality = quality;
+    self.position_offset_secs = 0.0;
+    self.finished_notified = false;
     if let Some(buf) = &self.buffer {
       buf.clear();
     }
+    // 前の曲の積み残しを新しい曲のバッファへ流し込んでしまわないよう破棄する
+    self.clear_pending();
+
+    // 再生・シーク・長さ計算で使い回せるよう、ロード時に一度だけデコードしておく
+    let (samples, sample_rate, channels) = self.decode_mp3_to_f32()?;
+    self.decoded = Some(Arc::new(DecodedAudio {
+      samples,
+      sample_rate,
+      channels,
+    }));
+
     Ok(())
   }
 
@@ -303,26 +674,90 @@ impl PlayerInner {
     Ok((samples, source_sample_rate, source_channels))
   }
 
+  /// デコード済みのPCMの指定フレーム位置から再生を開始する（曲の先頭から再生する場合は`start_frame = 0`）。
+  /// `start_paused`が`true`の場合、バッファ/AudioUnitを動かさず一時停止状態のまま積み込む
+  fn play_from_source_frame(&mut self, start_frame: usize, start_paused: bool) -> Result<(), String> {
+    let decoded = self
+      .decoded
+      .clone()
+      .ok_or_else(|| "MP3が未選択です".to_string())?;
+
+    let channels = decoded.channels as usize;
+    let start_sample = start_frame.saturating_mul(channels).min(decoded.samples.len());
+
+    self.position_offset_secs = start_frame as f64 / decoded.sample_rate as f64;
+    self.finished_notified = false;
+
+    let slice = decoded.samples[start_sample..].to_vec();
+    self.play_samples(slice, decoded.sample_rate, decoded.channels, start_paused)
+  }
+
+  /// 指定秒数へシークする: バッファを空にし、その位置から再生を再開する
+  fn seek(&mut self, position_secs: f64) -> Result<(), String> {
+    let decoded = self
+      .decoded
+      .clone()
+      .ok_or_else(|| "MP3が未選択です".to_string())?;
+
+    let frame_count = decoded.samples.len() / decoded.channels.max(1) as usize;
+    let start_frame = ((position_secs.max(0.0)) * decoded.sample_rate as f64) as usize;
+    let start_frame = start_frame.min(frame_count);
+
+    // シーク前が一時停止中だったかを覚えておき、一時停止中だった場合は
+    // バッファ/AudioUnitを動かさないまま積み直す（一時停止中の曲をシークすると
+    // 再生が始まってしまうのを防ぐ。以前はここで一度再生してから止めていたため、
+    // 一瞬だけ音が鳴る不具合があった）
+    let was_paused = self.buffer.as_ref().map(|buf| buf.is_paused()).unwrap_or(false);
+
+    if let Some(buf) = &self.buffer {
+      buf.clear();
+    }
+
+    self.play_from_source_frame(start_frame, was_paused)
+  }
+
   fn play_samples(
     &mut self,
     samples: Vec<f32>,
     source_rate: u32,
     source_channels: u16,
+    start_paused: bool,
   ) -> Result<(), String> {
     self.ensure_output()?;
 
+    let target_rate = self.sample_rate as u32;
+    let target_channels = self.output_channels; // 実際の出力デバイスのチャンネル数
+
+    // サンプルレートとチャンネル数を変換
+    let converted = convert_audio(
+      &samples,
+      source_rate,
+      source_channels,
+      target_rate,
+      target_channels,
+      self.resample_quality,
+    );
+
+    // リングバッファの容量（約5.5秒分）を超える曲は一度に積み切れないため、
+    // 積み残しは`pending_samples`として保持し、`refill`で少しずつ追加投入する
+    self.fully_queued = converted.is_empty();
+    self.pending_samples = converted;
+    self.pending_cursor = 0;
+    self.refill();
+
     let buffer = self
       .buffer
       .as_ref()
       .ok_or("バッファが初期化されていません")?;
 
-    let target_rate = self.sample_rate as u32;
-    let target_channels = 2u16; // CoreAudioは通常ステレオ
-
-    // サンプルレートとチャンネル数を変換
-    let converted = convert_audio(&samples, source_rate, source_channels, target_rate, target_channels);
+    if start_paused {
+      // 一時停止中の曲をシークした場合はここで止める: バッファ/AudioUnitを
+      // 一切動かさないことで、一度鳴ってから止める方式で生じていた一瞬の
+      // ブリップ音を避ける
+      buffer.pause();
+      return Ok(());
+    }
 
-    buffer.push_samples(&converted);
     buffer.resume();
 
     // AudioUnitを開始
@@ -332,6 +767,143 @@ impl PlayerInner {
 
     Ok(())
   }
+
+  /// `pending_samples`の残りを、リングバッファの空き容量に収まるだけ追加で積む。
+  /// オーディオスレッドのポーリング（`run_audio_thread`）から定期的に呼ばれる想定
+  fn refill(&mut self) {
+    if self.fully_queued {
+      return;
+    }
+
+    let Some(buffer) = &self.buffer else {
+      return;
+    };
+
+    let remaining = self.pending_samples.len() - self.pending_cursor;
+    if remaining == 0 {
+      self.clear_pending();
+      return;
+    }
+
+    let free = buffer.free_len();
+    if free == 0 {
+      return;
+    }
+
+    let take = free.min(remaining);
+    let start = self.pending_cursor;
+    buffer.push_samples(&self.pending_samples[start..start + take]);
+    self.pending_cursor += take;
+
+    if self.pending_cursor >= self.pending_samples.len() {
+      self.clear_pending();
+    }
+  }
+
+  /// 積み残しサンプルを破棄し、「全て積み終えた」状態に戻す
+  fn clear_pending(&mut self) {
+    self.pending_samples = Vec::new();
+    self.pending_cursor = 0;
+    self.fully_queued = true;
+  }
+
+  /// 再生が終端まで達したかを確認し、達していれば`audio://playback-finished`を送出する。
+  /// デコード済みサンプルを全て積み終えた（`fully_queued`）上でバッファが空になった
+  /// （`is_draining`）場合のみを終端とみなし、積み残し補充が追いつかないだけの一時的な
+  /// アンダーランと区別する。オーディオスレッドのポーリング（`run_audio_thread`）から
+  /// 定期的に呼ばれる想定
+  fn check_finished(&mut self, player_id: PlayerId, app_handle: &AppHandle) {
+    // `decoded`はMP3専用のキャッシュで、PCM直接再生(`play_samples`)では設定されない
+    // ため、ここでは見ない。「まだ何も積んでいない」ケースは`fully_queued`の
+    // 初期値`true`と組み合わさっても、その場合は`buffer`が未生成か、積まれた実体が
+    // 一度もないぶん`is_draining`が`false`のままなので、後続のチェックで弾かれる
+    if self.finished_notified || !self.fully_queued {
+      return;
+    }
+
+    let Some(buffer) = &self.buffer else {
+      return;
+    };
+
+    // `drained`は次の`clear`まで立ったままなので、今まさにバッファが空であることも確かめる
+    if buffer.is_paused() || !buffer.is_draining() || !buffer.is_empty() {
+      return;
+    }
+
+    self.finished_notified = true;
+    // バッファ自体は一時停止しない: ここで`pause`すると`is_paused()`がtrueになり、
+    // 次の再生操作がTogglePlaybackの「一時停止中なら再開」分岐に取られてしまい、
+    // 曲の先頭からの再生開始（`play_from_source_frame(0)`）に進めなくなる
+    if let Some(au) = self.audio_unit.as_mut() {
+      let _ = au.stop();
+    }
+
+    let _ = app_handle.emit("audio://playback-finished", player_id);
+  }
+}
+
+/// デフォルト出力デバイス変更・デバイス一覧変更のプロパティリスナーを
+/// システムオブジェクトに登録する。リスナーはアプリ終了まで有効であり続ける前提で
+/// コンテキストをリークする
+fn install_hotplug_listeners(tx: mpsc::Sender<AudioCommand>, app_handle: AppHandle) {
+  let default_device_address = AudioObjectPropertyAddress {
+    mSelector: kAudioHardwarePropertyDefaultOutputDevice,
+    mScope: kAudioObjectPropertyScopeGlobal,
+    mElement: kAudioObjectPropertyElementMain,
+  };
+
+  let tx_ctx = Box::into_raw(Box::new(tx)) as *mut std::ffi::c_void;
+  unsafe {
+    coreaudio::sys::AudioObjectAddPropertyListener(
+      kAudioObjectSystemObject,
+      &default_device_address,
+      Some(on_default_output_device_changed),
+      tx_ctx,
+    );
+  }
+
+  let devices_address = AudioObjectPropertyAddress {
+    mSelector: kAudioHardwarePropertyDevices,
+    mScope: kAudioObjectPropertyScopeGlobal,
+    mElement: kAudioObjectPropertyElementMain,
+  };
+
+  let handle_ctx = Box::into_raw(Box::new(app_handle)) as *mut std::ffi::c_void;
+  unsafe {
+    coreaudio::sys::AudioObjectAddPropertyListener(
+      kAudioObjectSystemObject,
+      &devices_address,
+      Some(on_devices_changed),
+      handle_ctx,
+    );
+  }
+}
+
+/// `kAudioHardwarePropertyDefaultOutputDevice`変更時のCoreAudioコールバック
+extern "C" fn on_default_output_device_changed(
+  _object_id: coreaudio::sys::AudioObjectID,
+  _num_addresses: u32,
+  _addresses: *const AudioObjectPropertyAddress,
+  client_data: *mut std::ffi::c_void,
+) -> coreaudio::sys::OSStatus {
+  let tx = unsafe { &*(client_data as *const mpsc::Sender<AudioCommand>) };
+  let _ = tx.send(AudioCommand::DefaultDeviceChanged);
+  0
+}
+
+/// `kAudioHardwarePropertyDevices`変更時のCoreAudioコールバック。
+/// デバイス一覧を再取得し、`audio://devices-changed`イベントでフロントエンドへ通知する
+extern "C" fn on_devices_changed(
+  _object_id: coreaudio::sys::AudioObjectID,
+  _num_addresses: u32,
+  _addresses: *const AudioObjectPropertyAddress,
+  client_data: *mut std::ffi::c_void,
+) -> coreaudio::sys::OSStatus {
+  let app_handle = unsafe { &*(client_data as *const AppHandle) };
+  if let Ok(devices) = audio_list_output_devices() {
+    let _ = app_handle.emit("audio://devices-changed", devices);
+  }
+  0
 }
 
 /// CoreAudioからデフォルト出力デバイスIDを取得
@@ -366,9 +938,40 @@ fn get_default_output_device() -> Result<AudioDeviceID, String> {
   Ok(device_id)
 }
 
-/// CoreAudioから全出力デバイスを取得
-fn get_all_output_devices() -> Result<Vec<(AudioDeviceID, String)>, String> {
-  // デバイスIDのリストを取得
+/// CoreAudioからデフォルト入力デバイスIDを取得
+fn get_default_input_device() -> Result<AudioDeviceID, String> {
+  let property_address = AudioObjectPropertyAddress {
+    mSelector: kAudioHardwarePropertyDefaultInputDevice,
+    mScope: kAudioObjectPropertyScopeGlobal,
+    mElement: kAudioObjectPropertyElementMain,
+  };
+
+  let mut device_id: AudioDeviceID = 0;
+  let mut data_size = std::mem::size_of::<AudioDeviceID>() as u32;
+
+  let status = unsafe {
+    AudioObjectGetPropertyData(
+      kAudioObjectSystemObject,
+      &property_address,
+      0,
+      std::ptr::null(),
+      &mut data_size,
+      &mut device_id as *mut _ as *mut _,
+    )
+  };
+
+  if status != 0 {
+    return Err(format!(
+      "デフォルト入力デバイスの取得に失敗しました (status: {})",
+      status
+    ));
+  }
+
+  Ok(device_id)
+}
+
+/// CoreAudioから全入力デバイスを取得
+fn get_all_input_devices() -> Result<Vec<(AudioDeviceID, String)>, String> {
   let property_address = AudioObjectPropertyAddress {
     mSelector: kAudioHardwarePropertyDevices,
     mScope: kAudioObjectPropertyScopeGlobal,
@@ -408,11 +1011,10 @@ fn get_all_output_devices() -> Result<Vec<(AudioDeviceID, String)>, String> {
     return Err(format!("デバイスリストの取得に失敗 (status: {})", status));
   }
 
-  // 各デバイスが出力をサポートしているかチェックし、名前を取得
   let mut result = Vec::new();
 
   for device_id in device_ids {
-    if has_output_streams(device_id) {
+    if has_input_streams(device_id) {
       if let Ok(name) = get_device_name(device_id) {
         result.push((device_id, name));
       }
@@ -422,18 +1024,19 @@ fn get_all_output_devices() -> Result<Vec<(AudioDeviceID, String)>, String> {
   Ok(result)
 }
 
-/// デバイスが出力ストリームを持っているかチェック
-fn has_output_streams(device_id: AudioDeviceID) -> bool {
+/// CoreAudioから全出力デバイスを取得
+fn get_all_output_devices() -> Result<Vec<(AudioDeviceID, String)>, String> {
+  // デバイスIDのリストを取得
   let property_address = AudioObjectPropertyAddress {
-    mSelector: kAudioDevicePropertyStreams,
-    mScope: kAudioObjectPropertyScopeOutput,
+    mSelector: kAudioHardwarePropertyDevices,
+    mScope: kAudioObjectPropertyScopeGlobal,
     mElement: kAudioObjectPropertyElementMain,
   };
 
   let mut data_size: u32 = 0;
   let status = unsafe {
     AudioObjectGetPropertyDataSize(
-      device_id,
+      kAudioObjectSystemObject,
       &property_address,
       0,
       std::ptr::null(),
@@ -441,32 +1044,97 @@ fn has_output_streams(device_id: AudioDeviceID) -> bool {
     )
   };
 
-  status == 0 && data_size > 0
-}
-
-/// デバイス名を取得
-fn get_device_name(device_id: AudioDeviceID) -> Result<String, String> {
-  let property_address = AudioObjectPropertyAddress {
-    mSelector: kAudioDevicePropertyDeviceNameCFString,
-    mScope: kAudioObjectPropertyScopeGlobal,
-    mElement: kAudioObjectPropertyElementMain,
-  };
+  if status != 0 {
+    return Err(format!("デバイスリストサイズの取得に失敗 (status: {})", status));
+  }
 
-  let mut name_ref: coreaudio::sys::CFStringRef = std::ptr::null();
-  let mut data_size = std::mem::size_of::<coreaudio::sys::CFStringRef>() as u32;
+  let device_count = data_size as usize / std::mem::size_of::<AudioDeviceID>();
+  let mut device_ids: Vec<AudioDeviceID> = vec![0; device_count];
 
   let status = unsafe {
     AudioObjectGetPropertyData(
-      device_id,
+      kAudioObjectSystemObject,
       &property_address,
       0,
       std::ptr::null(),
       &mut data_size,
-      &mut name_ref as *mut _ as *mut _,
+      device_ids.as_mut_ptr() as *mut _,
     )
   };
 
-  if status != 0 || name_ref.is_null() {
+  if status != 0 {
+    return Err(format!("デバイスリストの取得に失敗 (status: {})", status));
+  }
+
+  // 各デバイスが出力をサポートしているかチェックし、名前を取得
+  let mut result = Vec::new();
+
+  for device_id in device_ids {
+    if has_output_streams(device_id) {
+      if let Ok(name) = get_device_name(device_id) {
+        result.push((device_id, name));
+      }
+    }
+  }
+
+  Ok(result)
+}
+
+/// デバイスが出力ストリームを持っているかチェック
+fn has_output_streams(device_id: AudioDeviceID) -> bool {
+  has_streams_in_scope(device_id, kAudioObjectPropertyScopeOutput)
+}
+
+/// デバイスが入力ストリームを持っているかチェック
+fn has_input_streams(device_id: AudioDeviceID) -> bool {
+  has_streams_in_scope(device_id, kAudioObjectPropertyScopeInput)
+}
+
+/// 指定スコープにストリームを持っているかチェック
+fn has_streams_in_scope(device_id: AudioDeviceID, scope: coreaudio::sys::AudioObjectPropertyScope) -> bool {
+  let property_address = AudioObjectPropertyAddress {
+    mSelector: kAudioDevicePropertyStreams,
+    mScope: scope,
+    mElement: kAudioObjectPropertyElementMain,
+  };
+
+  let mut data_size: u32 = 0;
+  let status = unsafe {
+    AudioObjectGetPropertyDataSize(
+      device_id,
+      &property_address,
+      0,
+      std::ptr::null(),
+      &mut data_size,
+    )
+  };
+
+  status == 0 && data_size > 0
+}
+
+/// デバイス名を取得
+fn get_device_name(device_id: AudioDeviceID) -> Result<String, String> {
+  let property_address = AudioObjectPropertyAddress {
+    mSelector: kAudioDevicePropertyDeviceNameCFString,
+    mScope: kAudioObjectPropertyScopeGlobal,
+    mElement: kAudioObjectPropertyElementMain,
+  };
+
+  let mut name_ref: coreaudio::sys::CFStringRef = std::ptr::null();
+  let mut data_size = std::mem::size_of::<coreaudio::sys::CFStringRef>() as u32;
+
+  let status = unsafe {
+    AudioObjectGetPropertyData(
+      device_id,
+      &property_address,
+      0,
+      std::ptr::null(),
+      &mut data_size,
+      &mut name_ref as *mut _ as *mut _,
+    )
+  };
+
+  if status != 0 || name_ref.is_null() {
     return Err("デバイス名の取得に失敗".to_string());
   }
 
@@ -499,10 +1167,76 @@ fn get_device_name(device_id: AudioDeviceID) -> Result<String, String> {
   Ok(name)
 }
 
+/// デバイスの出力スコープにおける実チャンネル数を取得する。取得できない場合はステレオにフォールバックする
+fn get_output_channel_count(device_id: AudioDeviceID) -> u16 {
+  get_channel_count_in_scope(device_id, kAudioObjectPropertyScopeOutput)
+}
+
+/// デバイスの入力スコープにおける実チャンネル数を取得する。取得できない場合はステレオにフォールバックする
+fn get_input_channel_count(device_id: AudioDeviceID) -> u16 {
+  get_channel_count_in_scope(device_id, kAudioObjectPropertyScopeInput)
+}
+
+/// 指定スコープにおける実チャンネル数を取得する。取得できない場合はステレオにフォールバックする
+fn get_channel_count_in_scope(
+  device_id: AudioDeviceID,
+  scope: coreaudio::sys::AudioObjectPropertyScope,
+) -> u16 {
+  let property_address = AudioObjectPropertyAddress {
+    mSelector: coreaudio::sys::kAudioDevicePropertyStreamConfiguration,
+    mScope: scope,
+    mElement: kAudioObjectPropertyElementMain,
+  };
+
+  let mut data_size: u32 = 0;
+  let status = unsafe {
+    AudioObjectGetPropertyDataSize(
+      device_id,
+      &property_address,
+      0,
+      std::ptr::null(),
+      &mut data_size,
+    )
+  };
+  if status != 0 || data_size == 0 {
+    return 2;
+  }
+
+  let mut raw = vec![0u8; data_size as usize];
+  let status = unsafe {
+    AudioObjectGetPropertyData(
+      device_id,
+      &property_address,
+      0,
+      std::ptr::null(),
+      &mut data_size,
+      raw.as_mut_ptr() as *mut _,
+    )
+  };
+  if status != 0 {
+    return 2;
+  }
+
+  let buffer_list = raw.as_ptr() as *const coreaudio::sys::AudioBufferList;
+  let num_buffers = unsafe { (*buffer_list).mNumberBuffers } as usize;
+  let buffers_ptr = unsafe { (*buffer_list).mBuffers.as_ptr() };
+
+  let total_channels: u32 = (0..num_buffers)
+    .map(|i| unsafe { (*buffers_ptr.add(i)).mNumberChannels })
+    .sum();
+
+  if total_channels == 0 {
+    2
+  } else {
+    total_channels.min(MAX_RENDER_CHANNELS as u32) as u16
+  }
+}
+
 /// 指定デバイス用のAudioUnitを作成
 fn create_audio_unit_for_device(
   device_id: AudioDeviceID,
-) -> Result<(AudioUnit, f64, Arc<SharedBuffer>), String> {
+  existing_buffer: Option<Arc<SharedBuffer>>,
+) -> Result<(AudioUnit, f64, u16, Arc<SharedBuffer>), String> {
   // HAL Output AudioUnitを作成
   let mut audio_unit = AudioUnit::new(coreaudio::audio_unit::IOType::HalOutput)
     .map_err(|e| format!("AudioUnit作成に失敗: {:?}", e))?;
@@ -517,13 +1251,14 @@ fn create_audio_unit_for_device(
     )
     .map_err(|e| format!("出力デバイスの設定に失敗: {:?}", e))?;
 
-  // ストリームフォーマットを設定（48kHz, ステレオ, f32, Non-Interleaved）
+  // ストリームフォーマットを設定（48kHz, デバイスの実チャンネル数, f32, Non-Interleaved）
   let sample_rate = 48000.0;
+  let channels = get_output_channel_count(device_id);
   let stream_format = StreamFormat {
     sample_rate,
     sample_format: SampleFormat::F32,
     flags: LinearPcmFlags::IS_FLOAT | LinearPcmFlags::IS_NON_INTERLEAVED,
-    channels: 2,
+    channels,
   };
 
   audio_unit
@@ -535,8 +1270,9 @@ fn create_audio_unit_for_device(
     )
     .map_err(|e| format!("ストリームフォーマットの設定に失敗: {:?}", e))?;
 
-  // 共有バッファを作成
-  let buffer = SharedBuffer::new();
+  // 既存の共有バッファがあれば再利用し（ホットプラグ時にバッファ内容を保持するため）、
+  // なければ新規に作成する
+  let buffer = existing_buffer.unwrap_or_else(SharedBuffer::new);
   let buffer_clone = Arc::clone(&buffer);
 
   // レンダーコールバックを設定
@@ -548,6 +1284,10 @@ fn create_audio_unit_for_device(
         num_frames, mut data, ..
       } = args;
 
+      // 一時停止中は`pop_into`を呼ばないため、`clear`からのリセット依頼を
+      // 取りこぼさないようここで毎回適用しておく
+      buffer_clone.apply_pending_reset();
+
       if buffer_clone.is_paused() {
         for channel in data.channels_mut() {
           for sample in channel {
@@ -557,24 +1297,15 @@ fn create_audio_unit_for_device(
         return Ok(());
       }
 
-      if let Ok(mut buf) = buffer_clone.samples.lock() {
-        // チャンネルデータを収集
-        let mut channels: Vec<&mut [f32]> = data.channels_mut().collect();
-        let num_channels = channels.len();
-
-        // ステレオ出力（インターリーブされたデータをデインターリーブ）
-        for frame in 0..num_frames {
-          for ch in 0..num_channels {
-            // インターリーブされたサンプル: [L0, R0, L1, R1, ...]
-            let sample = buf.pop_front().unwrap_or(0.0);
-            channels[ch][frame] = sample;
-          }
-        }
-      } else {
-        for channel in data.channels_mut() {
-          for sample in channel {
-            *sample = 0.0;
-          }
+      // リングバッファから1フレーム分ずつ取り出し、NonInterleavedの各チャンネルへ配る
+      // （固定長スタックバッファを使い、リアルタイムスレッドでのヒープ確保を避ける）
+      let num_channels = data.channels().count().min(MAX_RENDER_CHANNELS);
+      let mut frame = [0.0f32; MAX_RENDER_CHANNELS];
+
+      for frame_idx in 0..num_frames {
+        buffer_clone.pop_into(&mut frame[..num_channels]);
+        for (ch, channel) in data.channels_mut().take(num_channels).enumerate() {
+          channel[frame_idx] = frame[ch];
         }
       }
 
@@ -582,7 +1313,308 @@ fn create_audio_unit_for_device(
     })
     .map_err(|e| format!("レンダーコールバックの設定に失敗: {:?}", e))?;
 
-  Ok((audio_unit, sample_rate, buffer))
+  Ok((audio_unit, sample_rate, channels, buffer))
+}
+
+/// 指定デバイス用の録音用AudioUnitを作成（入力スコープ）
+fn create_audio_input_unit_for_device(
+  device_id: AudioDeviceID,
+) -> Result<(AudioUnit, f64, Arc<RecordBuffer>), String> {
+  // HAL Output AudioUnitを入力モードで使う
+  let mut audio_unit = AudioUnit::new(coreaudio::audio_unit::IOType::HalOutput)
+    .map_err(|e| format!("AudioUnit作成に失敗: {:?}", e))?;
+
+  // 入力を有効化し、出力を無効化
+  audio_unit
+    .set_property(
+      coreaudio::sys::kAudioOutputUnitProperty_EnableIO,
+      Scope::Input,
+      Element::Input,
+      Some(&1u32),
+    )
+    .map_err(|e| format!("入力の有効化に失敗: {:?}", e))?;
+  audio_unit
+    .set_property(
+      coreaudio::sys::kAudioOutputUnitProperty_EnableIO,
+      Scope::Output,
+      Element::Output,
+      Some(&0u32),
+    )
+    .map_err(|e| format!("出力の無効化に失敗: {:?}", e))?;
+
+  // 入力デバイスを設定
+  audio_unit
+    .set_property(
+      coreaudio::sys::kAudioOutputUnitProperty_CurrentDevice,
+      Scope::Global,
+      Element::Output,
+      Some(&device_id),
+    )
+    .map_err(|e| format!("入力デバイスの設定に失敗: {:?}", e))?;
+
+  // ストリームフォーマットを設定（48kHz, デバイスの実チャンネル数, f32, Non-Interleaved）
+  let sample_rate = 48000.0;
+  let channels = get_input_channel_count(device_id);
+  let stream_format = StreamFormat {
+    sample_rate,
+    sample_format: SampleFormat::F32,
+    flags: LinearPcmFlags::IS_FLOAT | LinearPcmFlags::IS_NON_INTERLEAVED,
+    channels,
+  };
+
+  audio_unit
+    .set_property(
+      coreaudio::sys::kAudioUnitProperty_StreamFormat,
+      Scope::Output,
+      Element::Input,
+      Some(&stream_format.to_asbd()),
+    )
+    .map_err(|e| format!("ストリームフォーマットの設定に失敗: {:?}", e))?;
+
+  let record_buffer = RecordBuffer::new(channels);
+  let record_buffer_clone = Arc::clone(&record_buffer);
+
+  // 入力コールバックでキャプチャしたフレームをバッファへ積む
+  type InputArgs = render_callback::Args<data::NonInterleaved<f32>>;
+
+  audio_unit
+    .set_input_callback(move |args: InputArgs| {
+      let InputArgs { mut data, .. } = args;
+
+      let channels: Vec<&mut [f32]> = data.channels_mut().collect();
+      let num_channels = channels.len().max(1);
+      let num_frames = channels.first().map(|c| c.len()).unwrap_or(0);
+
+      let mut interleaved = Vec::with_capacity(num_frames * num_channels);
+      for frame in 0..num_frames {
+        for channel in &channels {
+          interleaved.push(channel[frame]);
+        }
+      }
+
+      record_buffer_clone.push_samples(&interleaved);
+      Ok(())
+    })
+    .map_err(|e| format!("入力コールバックの設定に失敗: {:?}", e))?;
+
+  audio_unit
+    .start()
+    .map_err(|e| format!("録音開始に失敗: {:?}", e))?;
+
+  Ok((audio_unit, sample_rate, record_buffer))
+}
+
+/// f32サンプル列をWAV（16bit PCM）にエンコード
+fn encode_wav_pcm16(samples: &[f32], sample_rate: u32, channels: u16) -> Vec<u8> {
+  let bits_per_sample: u16 = 16;
+  let block_align = channels * (bits_per_sample / 8);
+  let byte_rate = sample_rate * block_align as u32;
+  let data_size = (samples.len() * (bits_per_sample as usize / 8)) as u32;
+
+  let mut wav = Vec::with_capacity(44 + data_size as usize);
+
+  wav.extend_from_slice(b"RIFF");
+  wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+  wav.extend_from_slice(b"WAVE");
+
+  wav.extend_from_slice(b"fmt ");
+  wav.extend_from_slice(&16u32.to_le_bytes()); // fmtチャンクサイズ
+  wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+  wav.extend_from_slice(&channels.to_le_bytes());
+  wav.extend_from_slice(&sample_rate.to_le_bytes());
+  wav.extend_from_slice(&byte_rate.to_le_bytes());
+  wav.extend_from_slice(&block_align.to_le_bytes());
+  wav.extend_from_slice(&bits_per_sample.to_le_bytes());
+
+  wav.extend_from_slice(b"data");
+  wav.extend_from_slice(&data_size.to_le_bytes());
+
+  for &sample in samples {
+    let clamped = sample.clamp(-1.0, 1.0);
+    let pcm = (clamped * i16::MAX as f32) as i16;
+    wav.extend_from_slice(&pcm.to_le_bytes());
+  }
+
+  wav
+}
+
+/// スピーカー位置。既知のレイアウトの係数表を引くために使う
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Speaker {
+  FrontLeft,
+  FrontRight,
+  Center,
+  Lfe,
+  SurroundLeft,
+  SurroundRight,
+  Unknown,
+}
+
+/// 入出力のチャンネルレイアウト。チャンネル数から標準的な並びを推定する
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChannelLayout {
+  Mono,
+  Stereo,
+  Quad,
+  Surround51,
+  Other(usize),
+}
+
+impl ChannelLayout {
+  fn from_channel_count(channels: usize) -> Self {
+    match channels {
+      1 => ChannelLayout::Mono,
+      2 => ChannelLayout::Stereo,
+      4 => ChannelLayout::Quad,
+      6 => ChannelLayout::Surround51,
+      other => ChannelLayout::Other(other),
+    }
+  }
+
+  /// チャンネルインデックス順のスピーカー割り当て
+  fn speakers(&self) -> Vec<Speaker> {
+    use Speaker::*;
+    match self {
+      ChannelLayout::Mono => vec![Center],
+      ChannelLayout::Stereo => vec![FrontLeft, FrontRight],
+      ChannelLayout::Quad => vec![FrontLeft, FrontRight, SurroundLeft, SurroundRight],
+      ChannelLayout::Surround51 => {
+        vec![FrontLeft, FrontRight, Center, Lfe, SurroundLeft, SurroundRight]
+      }
+      ChannelLayout::Other(n) => (0..*n)
+        .map(|i| match i {
+          0 => FrontLeft,
+          1 => FrontRight,
+          _ => Unknown,
+        })
+        .collect(),
+    }
+  }
+}
+
+/// -3dB（1/√2）。センターやサラウンドをL/Rへ混ぜ込む際の標準的な減衰量
+const MINUS_3DB: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+/// `source`チャンネルから`target`チャンネルへの寄与係数
+fn downmix_coefficient(source: Speaker, target: Speaker, source_channels: usize, target_channels: usize) -> f32 {
+  use Speaker::*;
+
+  // LFEは定位情報を持たないため、ステレオ/モノへのダウンミックスでは捨てる
+  if source == Lfe && target_channels <= 2 {
+    return 0.0;
+  }
+
+  if target_channels == 1 {
+    // モノラル化: LFE以外の全チャンネルを均等に混ぜる
+    return if source == Lfe { 0.0 } else { 1.0 / source_channels as f32 };
+  }
+
+  let is_upmix = target_channels > source_channels;
+
+  match (source, target) {
+    (a, b) if a == b => 1.0,
+    // センターはL/Rへ-3dBで混ぜる（5.1→ステレオ等のダウンミックス）
+    (Center, FrontLeft) | (Center, FrontRight) if !is_upmix => MINUS_3DB,
+    // アップミックス: モノラルのセンターをL/Rへそのまま複製する
+    (Center, FrontLeft) | (Center, FrontRight) if is_upmix => 1.0,
+    // サラウンドchはL/Rへ-3dBで混ぜる（Quad/5.1→ステレオ等のダウンミックス）
+    (SurroundLeft, FrontLeft) | (SurroundRight, FrontRight) if !is_upmix => MINUS_3DB,
+    // アップミックス: フロントをサラウンドへそのまま複製する
+    (FrontLeft, SurroundLeft) | (FrontRight, SurroundRight) if is_upmix => 1.0,
+    // アップミックス: ファントムセンターをフロントL/Rから合成する
+    (FrontLeft, Center) | (FrontRight, Center) if is_upmix => 0.5,
+    _ => 0.0,
+  }
+}
+
+/// `out_channels x in_channels`のミキシングマトリクスを構築する
+fn build_mix_matrix(source_channels: usize, target_channels: usize) -> Vec<Vec<f32>> {
+  let source_speakers = ChannelLayout::from_channel_count(source_channels).speakers();
+  let target_speakers = ChannelLayout::from_channel_count(target_channels).speakers();
+
+  target_speakers
+    .iter()
+    .map(|&out_speaker| {
+      source_speakers
+        .iter()
+        .map(|&in_speaker| downmix_coefficient(in_speaker, out_speaker, source_channels, target_channels))
+        .collect()
+    })
+    .collect()
+}
+
+/// インターリーブされたサンプル列を、ミキシングマトリクスで別のチャンネル数へ変換する
+fn mix_channels(samples: &[f32], source_channels: usize, target_channels: usize) -> Vec<f32> {
+  let matrix = build_mix_matrix(source_channels, target_channels);
+  let frame_count = samples.len() / source_channels;
+
+  let mut result = Vec::with_capacity(frame_count * target_channels);
+  for frame_idx in 0..frame_count {
+    let base = frame_idx * source_channels;
+    let in_frame = &samples[base..base + source_channels];
+    for out_coeffs in &matrix {
+      let mixed: f32 = out_coeffs
+        .iter()
+        .zip(in_frame.iter())
+        .map(|(&coeff, &sample)| coeff * sample)
+        .sum();
+      result.push(mixed);
+    }
+  }
+
+  result
+}
+
+#[cfg(test)]
+mod mix_matrix_tests {
+  use super::*;
+
+  fn approx_eq(got: f32, want: f32) {
+    assert!((got - want).abs() < 1e-6, "expected {want}, got {got}");
+  }
+
+  #[test]
+  fn downmix_coefficient_mono_target_mixes_non_lfe_channels_equally() {
+    // モノラル化: LFE以外は均等混合（5.1→モノなら1/6ずつ）
+    approx_eq(downmix_coefficient(Speaker::FrontLeft, Speaker::Center, 6, 1), 1.0 / 6.0);
+    approx_eq(downmix_coefficient(Speaker::Lfe, Speaker::Center, 6, 1), 0.0);
+  }
+
+  #[test]
+  fn downmix_coefficient_surround51_to_stereo_spot_check() {
+    // 5.1→ステレオ: Center/サラウンドchは-3dBでL/Rへ、LFEは破棄
+    approx_eq(downmix_coefficient(Speaker::Center, Speaker::FrontLeft, 6, 2), MINUS_3DB);
+    approx_eq(downmix_coefficient(Speaker::Lfe, Speaker::FrontLeft, 6, 2), 0.0);
+    approx_eq(downmix_coefficient(Speaker::SurroundRight, Speaker::FrontRight, 6, 2), MINUS_3DB);
+    approx_eq(downmix_coefficient(Speaker::SurroundRight, Speaker::FrontLeft, 6, 2), 0.0);
+  }
+
+  #[test]
+  fn downmix_coefficient_mono_to_stereo_upmix_duplicates_center() {
+    // モノ→ステレオのアップミックスでは、センターをL/Rへそのまま複製する
+    approx_eq(downmix_coefficient(Speaker::Center, Speaker::FrontLeft, 1, 2), 1.0);
+    approx_eq(downmix_coefficient(Speaker::Center, Speaker::FrontRight, 1, 2), 1.0);
+  }
+
+  #[test]
+  fn build_mix_matrix_surround51_to_stereo() {
+    let matrix = build_mix_matrix(6, 2);
+    assert_eq!(matrix.len(), 2);
+    let expected_left = [1.0, 0.0, MINUS_3DB, 0.0, MINUS_3DB, 0.0];
+    let expected_right = [0.0, 1.0, MINUS_3DB, 0.0, 0.0, MINUS_3DB];
+    for (got, want) in matrix[0].iter().zip(expected_left.iter()) {
+      approx_eq(*got, *want);
+    }
+    for (got, want) in matrix[1].iter().zip(expected_right.iter()) {
+      approx_eq(*got, *want);
+    }
+  }
+
+  #[test]
+  fn mix_channels_mono_to_stereo_duplicates_signal() {
+    let mixed = mix_channels(&[1.0, -0.5], 1, 2);
+    assert_eq!(mixed, vec![1.0, 1.0, -0.5, -0.5]);
+  }
 }
 
 /// オーディオのサンプルレートとチャンネル数を変換
@@ -592,47 +1624,35 @@ fn convert_audio(
   source_channels: u16,
   target_rate: u32,
   target_channels: u16,
+  quality: ResampleQuality,
 ) -> Vec<f32> {
   let source_channels = source_channels as usize;
   let target_channels = target_channels as usize;
 
-  // まずチャンネル数を変換
-  let channel_converted: Vec<f32> = if source_channels == target_channels {
+  // チャンネルレイアウト間のミキシングマトリクスを使ってチャンネル数を変換
+  let channel_converted = if source_channels == target_channels {
     samples.to_vec()
-  } else if source_channels == 1 && target_channels == 2 {
-    // モノラル → ステレオ
-    samples.iter().flat_map(|&s| [s, s]).collect()
-  } else if source_channels == 2 && target_channels == 1 {
-    // ステレオ → モノラル
-    samples
-      .chunks(2)
-      .map(|chunk| {
-        if chunk.len() == 2 {
-          (chunk[0] + chunk[1]) / 2.0
-        } else {
-          chunk[0]
-        }
-      })
-      .collect()
   } else {
-    // その他の場合は最初のN個のチャンネルを使用
-    let frame_count = samples.len() / source_channels;
-    let mut result = Vec::with_capacity(frame_count * target_channels);
-    for frame_idx in 0..frame_count {
-      let base = frame_idx * source_channels;
-      for ch in 0..target_channels {
-        let src_ch = ch.min(source_channels - 1);
-        result.push(samples[base + src_ch]);
-      }
-    }
-    result
+    mix_channels(samples, source_channels, target_channels)
   };
 
-  // サンプルレートを変換（線形補間）
+  // サンプルレートを変換
   if source_rate == target_rate {
     return channel_converted;
   }
 
+  match quality {
+    ResampleQuality::Fast => {
+      resample_linear(&channel_converted, target_channels, source_rate, target_rate)
+    }
+    ResampleQuality::High => {
+      resample_sinc(&channel_converted, target_channels, source_rate, target_rate)
+    }
+  }
+}
+
+/// 線形補間によるリサンプリング（高速だが折り返しノイズが出やすい）
+fn resample_linear(channel_converted: &[f32], target_channels: usize, source_rate: u32, target_rate: u32) -> Vec<f32> {
   let frame_count = channel_converted.len() / target_channels;
   let ratio = source_rate as f64 / target_rate as f64;
   let new_frame_count = (frame_count as f64 / ratio) as usize;
@@ -658,9 +1678,170 @@ fn convert_audio(
   result
 }
 
+/// 帯域制限ポリフェーズsincフィルタによるリサンプリング
+fn resample_sinc(channel_converted: &[f32], target_channels: usize, source_rate: u32, target_rate: u32) -> Vec<f32> {
+  let frame_count = channel_converted.len() / target_channels;
+  let ratio = source_rate as f64 / target_rate as f64;
+  let new_frame_count = (frame_count as f64 / ratio) as usize;
+
+  let resampler = SincResampler::new(source_rate, target_rate);
+  let mut result = vec![0.0f32; new_frame_count * target_channels];
+
+  // チャンネルごとに独立して畳み込む
+  for ch in 0..target_channels {
+    let planar: Vec<f32> = (0..frame_count)
+      .map(|frame| channel_converted[frame * target_channels + ch])
+      .collect();
+    let resampled = resampler.process(&planar, new_frame_count, ratio);
+    for (frame, &sample) in resampled.iter().enumerate() {
+      result[frame * target_channels + ch] = sample;
+    }
+  }
+
+  result
+}
+
+/// 帯域制限windowed-sincローパスフィルタを使ったポリフェーズリサンプラ
+struct SincResampler {
+  /// ポリフェーズの分岐数（フィルタをこの数だけの位相にあらかじめ分割しておく）
+  phases: usize,
+  /// フィルタが片側に伸びるゼロ交差の数
+  taps: usize,
+  /// `coeffs[phase * (2*taps+1) + tap]` に格納した位相ごとのフィルタ係数
+  coeffs: Vec<f32>,
+}
+
+impl SincResampler {
+  const PHASES: usize = 32;
+  const TAPS: usize = 16;
+
+  fn new(source_rate: u32, target_rate: u32) -> Self {
+    let phases = Self::PHASES;
+    let taps = Self::TAPS;
+    let width = 2 * taps + 1;
+    // アップサンプリング時は1.0、ダウンサンプリング時はエイリアシング防止のためカットオフを下げる
+    let cutoff = (target_rate as f64 / source_rate as f64).min(1.0);
+
+    let mut coeffs = vec![0.0f32; phases * width];
+    for phase in 0..phases {
+      let frac = phase as f64 / phases as f64;
+      for t in 0..width {
+        // tap位置からフィルタ中心までの距離（入力サンプル単位）
+        let n = t as f64 - taps as f64 - frac;
+        let h = sinc(n * cutoff) * cutoff;
+        let window = blackman_window(t as f64, (width - 1) as f64);
+        coeffs[phase * width + t] = (h * window) as f32;
+      }
+    }
+
+    Self { phases, taps, coeffs }
+  }
+
+  /// `input`（1チャンネル分）を `ratio = source_rate/target_rate` で `out_len` フレームにリサンプルする
+  fn process(&self, input: &[f32], out_len: usize, ratio: f64) -> Vec<f32> {
+    let width = 2 * self.taps + 1;
+    let mut out = Vec::with_capacity(out_len);
+
+    for out_idx in 0..out_len {
+      let src_pos = out_idx as f64 * ratio;
+      let base_idx = src_pos.floor() as i64;
+      let frac = src_pos - base_idx as f64;
+      let phase = ((frac * self.phases as f64).round() as usize).min(self.phases - 1);
+      let phase_coeffs = &self.coeffs[phase * width..(phase + 1) * width];
+
+      let mut acc = 0.0f32;
+      for (t, &coeff) in phase_coeffs.iter().enumerate() {
+        let src_idx = base_idx + t as i64 - self.taps as i64;
+        if src_idx >= 0 {
+          if let Some(&sample) = input.get(src_idx as usize) {
+            acc += sample * coeff;
+          }
+        }
+      }
+      out.push(acc);
+    }
+
+    out
+  }
+}
+
+fn sinc(x: f64) -> f64 {
+  if x.abs() < 1e-8 {
+    1.0
+  } else {
+    let pix = std::f64::consts::PI * x;
+    pix.sin() / pix
+  }
+}
+
+/// Blackman窓（`n`は0..=big_nの範囲）
+fn blackman_window(n: f64, big_n: f64) -> f64 {
+  let a0 = 0.42;
+  let a1 = 0.5;
+  let a2 = 0.08;
+  let two_pi_n = 2.0 * std::f64::consts::PI * n / big_n;
+  a0 - a1 * two_pi_n.cos() + a2 * (2.0 * two_pi_n).cos()
+}
+
+#[cfg(test)]
+mod resample_tests {
+  use super::*;
+
+  fn approx_eq(got: f32, want: f32) {
+    assert!((got - want).abs() < 1e-4, "expected {want}, got {got}");
+  }
+
+  #[test]
+  fn resample_linear_upsamples_2x_by_interpolating() {
+    // source_rate=1, target_rate=2（2倍アップサンプリング）。モノラル2フレームを
+    // 4フレームへ補間する単純なケースを手計算で検証する
+    let output = resample_linear(&[0.0, 1.0], 1, 1, 2);
+    let expected = [0.0, 0.5, 1.0, 1.0];
+    assert_eq!(output.len(), expected.len());
+    for (got, want) in output.iter().zip(expected.iter()) {
+      approx_eq(*got, *want);
+    }
+  }
+
+  #[test]
+  fn resample_sinc_passes_silence_through_as_silence() {
+    let output = resample_sinc(&vec![0.0f32; 8], 1, 1, 2);
+    assert_eq!(output.len(), 16);
+    assert!(output.iter().all(|&s| s == 0.0));
+  }
+
+  #[test]
+  fn sinc_resampler_center_tap_is_unity_at_ratio_one() {
+    // source_rate == target_rateではcutoff=1.0となり、位相0（frac=0）の中心タップは
+    // sinc(0)*窓=1.0*1.0、すなわち理想的には入力をそのまま通す係数になるはず
+    let resampler = SincResampler::new(1, 1);
+    let width = 2 * SincResampler::TAPS + 1;
+    assert_eq!(resampler.coeffs.len(), SincResampler::PHASES * width);
+    approx_eq(resampler.coeffs[SincResampler::TAPS], 1.0);
+  }
+}
+
 fn run_audio_thread(rx: mpsc::Receiver<AudioCommand>) {
   let mut engine = AudioEngineInner::default();
-  while let Ok(cmd) = rx.recv() {
+  loop {
+    let cmd = match rx.recv_timeout(std::time::Duration::from_millis(100)) {
+      Ok(cmd) => cmd,
+      Err(mpsc::RecvTimeoutError::Timeout) => {
+        // コマンド待ちの合間に、容量を超える曲の積み残しをバッファへ補充しつつ、
+        // 再生が終端まで達したプレイヤーがいないか確認する
+        for player in engine.players.values_mut() {
+          player.refill();
+        }
+        if let Some(app_handle) = engine.app_handle.clone() {
+          for (player_id, player) in engine.players.iter_mut() {
+            player.check_finished(*player_id, &app_handle);
+          }
+        }
+        continue;
+      }
+      Err(mpsc::RecvTimeoutError::Disconnected) => break,
+    };
+
     match cmd {
       AudioCommand::CreatePlayer { respond_to } => {
         let _ = respond_to.send(Ok(engine.create_player()));
@@ -687,11 +1868,12 @@ fn run_audio_thread(rx: mpsc::Receiver<AudioCommand>) {
         player_id,
         bytes,
         file_name,
+        quality,
         respond_to,
       } => {
         let result = (|| {
           let player = engine.player_mut(player_id)?;
-          player.load_mp3(bytes, file_name)?;
+          player.load_mp3(bytes, file_name, quality)?;
           Ok(player.state(player_id))
         })();
         let _ = respond_to.send(result);
@@ -719,9 +1901,8 @@ fn run_audio_thread(rx: mpsc::Receiver<AudioCommand>) {
             }
           }
 
-          // バッファが空ならMP3をデコードして再生
-          let (samples, source_rate, source_channels) = player.decode_mp3_to_f32()?;
-          player.play_samples(samples, source_rate, source_channels)?;
+          // バッファが空なら曲の先頭から再生
+          player.play_from_source_frame(0, false)?;
           Ok(player.state(player_id))
         })();
         let _ = respond_to.send(result);
@@ -736,6 +1917,10 @@ fn run_audio_thread(rx: mpsc::Receiver<AudioCommand>) {
             buf.clear();
             buf.pause();
           }
+          player.position_offset_secs = 0.0;
+          // 積み残しを捨てておかないと、停止中でも次のポーリングでバッファへ
+          // 再投入されてしまい、再開時に停止時点の続きが再生されてしまう
+          player.clear_pending();
           Ok(player.state(player_id))
         })();
         let _ = respond_to.send(result);
@@ -754,6 +1939,7 @@ fn run_audio_thread(rx: mpsc::Receiver<AudioCommand>) {
         sample_rate,
         channels,
         samples,
+        quality,
         respond_to,
       } => {
         let result = (|| {
@@ -765,11 +1951,61 @@ fn run_audio_thread(rx: mpsc::Receiver<AudioCommand>) {
           }
 
           let player = engine.player_mut(player_id)?;
-          player.play_samples(samples, sample_rate, channels)?;
+          player.resample_quality = quality;
+          // MP3再生時の情報が残っていると、古い曲の長さ・再生位置を引きずったり、
+          // 既に`true`の`finished_notified`のせいでこの再生の終端通知が
+          // 飛ばなくなったりするため、PCM再生の開始時点で明示的にリセットする
+          player.decoded = None;
+          player.position_offset_secs = 0.0;
+          player.finished_notified = false;
+          player.play_samples(samples, sample_rate, channels, false)?;
+          Ok(player.state(player_id))
+        })();
+        let _ = respond_to.send(result);
+      }
+      AudioCommand::StartRecording {
+        player_id,
+        device_id,
+        respond_to,
+      } => {
+        let result = (|| {
+          let player = engine.player_mut(player_id)?;
+          player.start_recording(device_id)?;
+          Ok(player.state(player_id))
+        })();
+        let _ = respond_to.send(result);
+      }
+      AudioCommand::StopRecording {
+        player_id,
+        respond_to,
+      } => {
+        let result = (|| {
+          let player = engine.player_mut(player_id)?;
+          player.stop_recording()
+        })();
+        let _ = respond_to.send(result);
+      }
+      AudioCommand::DefaultDeviceChanged => {
+        // "default"デバイスを使っている全プレイヤーを、バッファを保持したまま繋ぎ直す
+        for player in engine.players.values_mut() {
+          let _ = player.rebuild_default_output();
+        }
+      }
+      AudioCommand::Seek {
+        player_id,
+        position_secs,
+        respond_to,
+      } => {
+        let result = (|| {
+          let player = engine.player_mut(player_id)?;
+          player.seek(position_secs)?;
           Ok(player.state(player_id))
         })();
         let _ = respond_to.send(result);
       }
+      AudioCommand::SetAppHandle { app_handle } => {
+        engine.app_handle = Some(app_handle);
+      }
     }
   }
 }
@@ -834,11 +2070,13 @@ pub fn audio_load_mp3(
   player_id: PlayerId,
   bytes: Vec<u8>,
   file_name: String,
+  quality: Option<ResampleQuality>,
 ) -> Result<AudioPlayerState, String> {
   state.call(|respond_to| AudioCommand::LoadMp3 {
     player_id,
     bytes,
     file_name,
+    quality: quality.unwrap_or_default(),
     respond_to,
   })
 }
@@ -883,12 +2121,74 @@ pub fn audio_play_pcm_f32(
   sample_rate: u32,
   channels: u16,
   samples: Vec<f32>,
+  quality: Option<ResampleQuality>,
 ) -> Result<AudioPlayerState, String> {
   state.call(|respond_to| AudioCommand::PlayPcmF32 {
     player_id,
     sample_rate,
     channels,
     samples,
+    quality: quality.unwrap_or_default(),
+    respond_to,
+  })
+}
+
+#[tauri::command]
+pub fn audio_list_input_devices() -> Result<Vec<AudioInputDevice>, String> {
+  let mut result = Vec::new();
+
+  let default_device_id = get_default_input_device()?;
+  let default_name = get_device_name(default_device_id).unwrap_or_else(|_| "不明".to_string());
+
+  result.push(AudioInputDevice {
+    id: "default".to_string(),
+    name: format!("システムデフォルト（{default_name}）"),
+  });
+
+  let devices = get_all_input_devices()?;
+  for (device_id, name) in devices {
+    result.push(AudioInputDevice {
+      id: device_id.to_string(),
+      name,
+    });
+  }
+
+  Ok(result)
+}
+
+#[tauri::command]
+pub fn audio_start_recording(
+  state: State<'_, AudioController>,
+  player_id: PlayerId,
+  device_id: String,
+) -> Result<AudioPlayerState, String> {
+  state.call(|respond_to| AudioCommand::StartRecording {
+    player_id,
+    device_id,
+    respond_to,
+  })
+}
+
+#[tauri::command]
+pub fn audio_stop_recording(
+  state: State<'_, AudioController>,
+  player_id: PlayerId,
+) -> Result<Vec<u8>, String> {
+  state.call(|respond_to| AudioCommand::StopRecording {
+    player_id,
+    respond_to,
+  })
+}
+
+#[tauri::command]
+pub fn audio_seek(
+  state: State<'_, AudioController>,
+  player_id: PlayerId,
+  position_secs: f64,
+) -> Result<AudioPlayerState, String> {
+  state.call(|respond_to| AudioCommand::Seek {
+    player_id,
+    position_secs,
     respond_to,
   })
 }
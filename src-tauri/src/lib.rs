@@ -1,5 +1,7 @@
 mod audio;
 
+use tauri::Manager;
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   tauri::Builder::default()
@@ -12,6 +14,10 @@ pub fn run() {
             .build(),
         )?;
       }
+
+      let controller = app.state::<audio::AudioController>().inner().clone();
+      controller.install_hotplug_listeners(app.handle().clone());
+
       Ok(())
     })
     .invoke_handler(tauri::generate_handler![
@@ -24,6 +30,10 @@ pub fn run() {
       audio::audio_stop,
       audio::audio_get_state,
       audio::audio_play_pcm_f32,
+      audio::audio_list_input_devices,
+      audio::audio_start_recording,
+      audio::audio_stop_recording,
+      audio::audio_seek,
     ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");